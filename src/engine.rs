@@ -1,20 +1,24 @@
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::{
     cell::RefCell,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
     ops::{Add, Div, Mul, Neg, Sub},
     rc::Rc,
 };
 
+use serde::Serialize;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Op {
     Add,
     Mul,
     Exp,
     Pow,
+    Ln,
     ReLU,
     TanH,
+    Sigmoid,
 }
 
 impl Display for Op {
@@ -32,12 +36,18 @@ impl Display for Op {
             Op::Pow => {
                 write!(f, "pow")
             }
+            Op::Ln => {
+                write!(f, "ln")
+            }
             Op::ReLU => {
                 write!(f, "relu")
             }
             Op::TanH => {
                 write!(f, "tanh")
             }
+            Op::Sigmoid => {
+                write!(f, "sigmoid")
+            }
         }
     }
 }
@@ -73,14 +83,23 @@ impl Value {
     }
 
     pub fn pow(&self, exponent: f64) -> Self {
+        // The exponent is carried as a second (constant) child, the same way
+        // `Add<f64>`/`Mul<f64>` wrap a bare `f64` as a leaf `Value`, so
+        // `backward` can recover it via `rvalue()` instead of losing it.
+        let exponent_value = Value::new(exponent, vec![], None);
         let result = Self::new(
             self.0.borrow().data.powf(exponent),
-            vec![self.clone()],
+            vec![self.clone(), exponent_value],
             Some(Op::Pow),
         );
         result
     }
 
+    pub fn ln(&self) -> Self {
+        let result = Self::new(self.0.borrow().data.ln(), vec![self.clone()], Some(Op::Ln));
+        result
+    }
+
     pub fn relu(&self) -> Self {
         let result = Self::new(
             self.0.borrow().data.max(0.0),
@@ -99,6 +118,15 @@ impl Value {
         result
     }
 
+    pub fn sigmoid(&self) -> Self {
+        let result = Self::new(
+            1.0 / (1.0 + (-self.0.borrow().data).exp()),
+            vec![self.clone()],
+            Some(Op::Sigmoid),
+        );
+        result
+    }
+
     pub fn op(&self) -> Option<Op> {
         self.0.borrow()._op.clone()
     }
@@ -153,9 +181,13 @@ impl Value {
                     value.lvalue().add_grad(value.grad() * value.data());
                 }
                 Some(Op::Pow) => {
+                    let base = value.lvalue();
+                    let exponent = value.rvalue().data();
+                    base.add_grad(value.grad() * (exponent * base.data().powf(exponent - 1.0)));
+                }
+                Some(Op::Ln) => {
                     let lvalue = value.lvalue();
-                    let lval = lvalue.data();
-                    lvalue.add_grad(value.grad() * (lval * value.data().powf(lval - 1.0)));
+                    lvalue.add_grad(value.grad() / lvalue.data());
                 }
                 Some(Op::ReLU) => {
                     let lvalue = value.lvalue();
@@ -168,18 +200,33 @@ impl Value {
                     let t = value.data();
                     lvalue.add_grad(value.grad() * (1.0 - t.powf(2.0)));
                 }
+                Some(Op::Sigmoid) => {
+                    let lvalue = value.lvalue();
+                    let s = value.data();
+                    lvalue.add_grad(value.grad() * (s * (1.0 - s)));
+                }
                 None => {}
             }
         }
 
-        fn topological_sort(v: &Value, topo: &mut Vec<Value>, set: &mut HashSet<Value>) {
-            // Topological sort algorithm to determine order of backward pass
+        let topo = self.topological_order();
 
+        // set self grad to 1.0
+        self.0.borrow_mut().grad = 1.0;
+        for value in topo.iter().rev() {
+            backward_prev(value);
+        }
+    }
+
+    /// Topologically sorts the DAG rooted at `self`, deduplicating shared
+    /// sub-expressions with the same `HashSet`-based walk `backward` uses.
+    fn topological_order(&self) -> Vec<Value> {
+        fn visit(v: &Value, topo: &mut Vec<Value>, set: &mut HashSet<Value>) {
             if !set.contains(v) {
                 set.insert(v.clone());
 
                 for child in &v.0.borrow()._prev {
-                    topological_sort(child, topo, set);
+                    visit(child, topo, set);
                 }
                 topo.push(v.clone());
             }
@@ -187,16 +234,82 @@ impl Value {
 
         let mut topo = Vec::new();
         let mut set = HashSet::new();
-        topological_sort(self, &mut topo, &mut set);
-
-        // set self grad to 1.0
-        self.0.borrow_mut().grad = 1.0;
-        for value in topo.iter().rev() {
-            backward_prev(value);
+        visit(self, &mut topo, &mut set);
+        topo
+    }
+
+    /// Exports the computation DAG rooted at `self` as a JSON document: one
+    /// entry per node with its `data`, `grad`, `Op`, and child edges, shared
+    /// sub-expressions emitted once thanks to `topological_order`'s dedup.
+    pub fn to_graph_json(&self) -> String {
+        let topo = self.topological_order();
+        let ids = Self::node_ids(&topo);
+
+        let nodes: Vec<GraphNode> = topo
+            .iter()
+            .enumerate()
+            .map(|(id, v)| GraphNode {
+                id,
+                data: v.data(),
+                grad: v.grad(),
+                op: v.op().map(|op| op.to_string()),
+                children: v.children().iter().map(|c| ids[&Rc::as_ptr(&c.0)]).collect(),
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&Graph { nodes }).expect("graph data always serializes")
+    }
+
+    /// Exports the computation DAG rooted at `self` as Graphviz DOT: each
+    /// `Value` is a node, each `Op` an intermediate box node feeding it.
+    pub fn to_dot(&self) -> String {
+        let topo = self.topological_order();
+        let ids = Self::node_ids(&topo);
+
+        let mut dot = String::from("digraph G {\n");
+        for (id, v) in topo.iter().enumerate() {
+            dot.push_str(&format!(
+                "  n{} [label=\"data={:.4}\\ngrad={:.4}\"];\n",
+                id,
+                v.data(),
+                v.grad()
+            ));
+
+            if let Some(op) = v.op() {
+                dot.push_str(&format!("  op{} [label=\"{}\", shape=box];\n", id, op));
+                dot.push_str(&format!("  op{} -> n{};\n", id, id));
+                for child in v.children() {
+                    dot.push_str(&format!("  n{} -> op{};\n", ids[&Rc::as_ptr(&child.0)], id));
+                }
+            }
         }
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    fn node_ids(topo: &[Value]) -> HashMap<*const RefCell<InnerValue>, usize> {
+        topo.iter()
+            .enumerate()
+            .map(|(id, v)| (Rc::as_ptr(&v.0), id))
+            .collect()
     }
 }
 
+#[derive(Serialize)]
+struct GraphNode {
+    id: usize,
+    data: f64,
+    grad: f64,
+    op: Option<String>,
+    children: Vec<usize>,
+}
+
+#[derive(Serialize)]
+struct Graph {
+    nodes: Vec<GraphNode>,
+}
+
 impl Debug for Value {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         Display::fmt(&self, f)
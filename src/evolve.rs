@@ -0,0 +1,528 @@
+use std::collections::{HashMap, HashSet};
+use std::iter::zip;
+
+use rand::{distributions::Uniform, prelude::Distribution, Rng};
+
+use crate::engine::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    Input,
+    Hidden,
+    Output,
+}
+
+#[derive(Clone, Debug)]
+pub struct NodeGene {
+    pub id: usize,
+    pub kind: NodeKind,
+}
+
+#[derive(Clone, Debug)]
+pub struct ConnectionGene {
+    pub in_node: usize,
+    pub out_node: usize,
+    pub weight: f64,
+    pub enabled: bool,
+    pub innovation: usize,
+}
+
+/// Assigns globally unique innovation numbers, reusing the same number for a
+/// given `(in_node, out_node)` pair so that identical structural mutations
+/// arising in different genomes can still be aligned during crossover.
+pub struct InnovationTracker {
+    next: usize,
+    seen: HashMap<(usize, usize), usize>,
+}
+
+impl InnovationTracker {
+    pub fn new() -> Self {
+        Self {
+            next: 0,
+            seen: HashMap::new(),
+        }
+    }
+
+    pub fn innovation_for(&mut self, in_node: usize, out_node: usize) -> usize {
+        if let Some(&innov) = self.seen.get(&(in_node, out_node)) {
+            return innov;
+        }
+
+        let innov = self.next;
+        self.next += 1;
+        self.seen.insert((in_node, out_node), innov);
+        innov
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Genome {
+    pub nodes: Vec<NodeGene>,
+    pub connections: Vec<ConnectionGene>,
+    n_inputs: usize,
+    n_outputs: usize,
+}
+
+impl Genome {
+    /// A minimal genome: every input connected directly to every output.
+    pub fn new(n_inputs: usize, n_outputs: usize, innovation: &mut InnovationTracker) -> Self {
+        let mut nodes = Vec::with_capacity(n_inputs + n_outputs);
+        for i in 0..n_inputs {
+            nodes.push(NodeGene {
+                id: i,
+                kind: NodeKind::Input,
+            });
+        }
+        for j in 0..n_outputs {
+            nodes.push(NodeGene {
+                id: n_inputs + j,
+                kind: NodeKind::Output,
+            });
+        }
+
+        let mut rng = rand::thread_rng();
+        let between = Uniform::new_inclusive(-1.0, 1.0);
+        let mut connections = Vec::with_capacity(n_inputs * n_outputs);
+        for i in 0..n_inputs {
+            for j in 0..n_outputs {
+                let out_node = n_inputs + j;
+                connections.push(ConnectionGene {
+                    in_node: i,
+                    out_node,
+                    weight: between.sample(&mut rng),
+                    enabled: true,
+                    innovation: innovation.innovation_for(i, out_node),
+                });
+            }
+        }
+
+        Self {
+            nodes,
+            connections,
+            n_inputs,
+            n_outputs,
+        }
+    }
+
+    fn next_node_id(&self) -> usize {
+        self.nodes.iter().map(|n| n.id).max().map_or(0, |m| m + 1)
+    }
+
+    /// Topological order obtained by repeatedly peeling off nodes whose
+    /// enabled incoming connections have already been resolved. Nodes left
+    /// unreachable (e.g. isolated by a cycle) are simply skipped.
+    fn topological_order(&self) -> Vec<usize> {
+        let mut incoming: HashMap<usize, Vec<usize>> = HashMap::new();
+        for node in &self.nodes {
+            incoming.entry(node.id).or_default();
+        }
+        for conn in self.connections.iter().filter(|c| c.enabled) {
+            incoming.entry(conn.out_node).or_default().push(conn.in_node);
+        }
+
+        let mut resolved: HashSet<usize> = self
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Input)
+            .map(|n| n.id)
+            .collect();
+        let mut order: Vec<usize> = resolved.iter().copied().collect();
+
+        while order.len() < self.nodes.len() {
+            let mut progressed = false;
+            for node in &self.nodes {
+                if resolved.contains(&node.id) {
+                    continue;
+                }
+                if incoming[&node.id].iter().all(|d| resolved.contains(d)) {
+                    order.push(node.id);
+                    resolved.insert(node.id);
+                    progressed = true;
+                }
+            }
+            if !progressed {
+                break;
+            }
+        }
+
+        order
+    }
+
+    /// Evaluates the genome on `inputs` (one `Value` per input node),
+    /// returning the output nodes' activations. Built entirely from `Value`
+    /// ops, so `backward()` works unchanged if gradient-assisted refinement
+    /// of the evolved weights is wanted on top of evolution.
+    pub fn evaluate(&self, inputs: &[Value]) -> Vec<Value> {
+        assert_eq!(inputs.len(), self.n_inputs);
+
+        let mut values: HashMap<usize, Value> = HashMap::new();
+        for (node, input) in zip(
+            self.nodes.iter().filter(|n| n.kind == NodeKind::Input),
+            inputs,
+        ) {
+            values.insert(node.id, input.clone());
+        }
+
+        for id in self.topological_order() {
+            if values.contains_key(&id) {
+                continue;
+            }
+
+            let sum = self
+                .connections
+                .iter()
+                .filter(|c| c.enabled && c.out_node == id)
+                .fold(Value::new(0.0, vec![], None), |acc, c| {
+                    acc + values[&c.in_node].clone() * c.weight
+                });
+            values.insert(id, sum.tanh());
+        }
+
+        self.nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Output)
+            .map(|n| values[&n.id].clone())
+            .collect()
+    }
+
+    pub fn mutate_weights(&mut self) {
+        let mut rng = rand::thread_rng();
+        let perturb = Uniform::new_inclusive(-0.1, 0.1);
+        for conn in self.connections.iter_mut() {
+            conn.weight += perturb.sample(&mut rng);
+        }
+    }
+
+    /// Set of node ids reachable from `start` by following enabled
+    /// connections forward. Used to reject candidate links that would close
+    /// a cycle, since `evaluate`'s topological walk assumes a DAG.
+    fn reachable_from(&self, start: usize) -> HashSet<usize> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+            for conn in self.connections.iter().filter(|c| c.enabled && c.in_node == node) {
+                stack.push(conn.out_node);
+            }
+        }
+        visited
+    }
+
+    /// Links two currently unconnected nodes with a fresh innovation number.
+    /// Candidates that already exist, or that would close a cycle through
+    /// existing connections, are rejected.
+    pub fn mutate_add_connection(&mut self, innovation: &mut InnovationTracker) {
+        let candidates: Vec<(usize, usize)> = self
+            .nodes
+            .iter()
+            .filter(|a| a.kind != NodeKind::Output)
+            .flat_map(|a| {
+                self.nodes
+                    .iter()
+                    .filter(move |b| b.kind != NodeKind::Input && b.id != a.id)
+                    .map(move |b| (a.id, b.id))
+            })
+            .filter(|(i, o)| {
+                !self
+                    .connections
+                    .iter()
+                    .any(|c| c.in_node == *i && c.out_node == *o)
+            })
+            .filter(|(i, o)| !self.reachable_from(*o).contains(i))
+            .collect();
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        let mut rng = rand::thread_rng();
+        let (in_node, out_node) = candidates[rng.gen_range(0..candidates.len())];
+        let between = Uniform::new_inclusive(-1.0, 1.0);
+
+        self.connections.push(ConnectionGene {
+            in_node,
+            out_node,
+            weight: between.sample(&mut rng),
+            enabled: true,
+            innovation: innovation.innovation_for(in_node, out_node),
+        });
+    }
+
+    /// Picks an enabled connection, disables it, and inserts a new node in
+    /// its place: the incoming half gets weight 1.0, the outgoing half
+    /// inherits the disabled connection's weight.
+    pub fn mutate_add_node(&mut self, innovation: &mut InnovationTracker) {
+        let enabled: Vec<usize> = self
+            .connections
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.enabled)
+            .map(|(i, _)| i)
+            .collect();
+        if enabled.is_empty() {
+            return;
+        }
+
+        let mut rng = rand::thread_rng();
+        let idx = enabled[rng.gen_range(0..enabled.len())];
+
+        let (in_node, out_node, weight) = {
+            let conn = &mut self.connections[idx];
+            conn.enabled = false;
+            (conn.in_node, conn.out_node, conn.weight)
+        };
+
+        let new_id = self.next_node_id();
+        self.nodes.push(NodeGene {
+            id: new_id,
+            kind: NodeKind::Hidden,
+        });
+
+        self.connections.push(ConnectionGene {
+            in_node,
+            out_node: new_id,
+            weight: 1.0,
+            enabled: true,
+            innovation: innovation.innovation_for(in_node, new_id),
+        });
+        self.connections.push(ConnectionGene {
+            in_node: new_id,
+            out_node,
+            weight,
+            enabled: true,
+            innovation: innovation.innovation_for(new_id, out_node),
+        });
+    }
+}
+
+/// Aligns two parents' connection genes by innovation number: matching genes
+/// are inherited randomly from either parent, disjoint/excess genes come
+/// from `fitter`.
+pub fn crossover(fitter: &Genome, other: &Genome) -> Genome {
+    let mut rng = rand::thread_rng();
+    let other_by_innov: HashMap<usize, &ConnectionGene> =
+        other.connections.iter().map(|c| (c.innovation, c)).collect();
+
+    let connections = fitter
+        .connections
+        .iter()
+        .map(|conn| match other_by_innov.get(&conn.innovation) {
+            Some(&matching) if rng.gen_bool(0.5) => matching.clone(),
+            _ => conn.clone(),
+        })
+        .collect();
+
+    Genome {
+        nodes: fitter.nodes.clone(),
+        connections,
+        n_inputs: fitter.n_inputs,
+        n_outputs: fitter.n_outputs,
+    }
+}
+
+/// Compatibility distance `c1*E/N + c2*D/N + c3*W`, with excess genes `E`,
+/// disjoint genes `D`, and `W` the mean weight difference of matching genes.
+pub fn compatibility_distance(g1: &Genome, g2: &Genome, c1: f64, c2: f64, c3: f64) -> f64 {
+    let innov1: HashMap<usize, &ConnectionGene> =
+        g1.connections.iter().map(|c| (c.innovation, c)).collect();
+    let innov2: HashMap<usize, &ConnectionGene> =
+        g2.connections.iter().map(|c| (c.innovation, c)).collect();
+
+    let max_innov1 = g1.connections.iter().map(|c| c.innovation).max().unwrap_or(0);
+    let max_innov2 = g2.connections.iter().map(|c| c.innovation).max().unwrap_or(0);
+
+    let mut matching = 0u32;
+    let mut weight_diff = 0.0;
+    let mut disjoint = 0u32;
+    let mut excess = 0u32;
+
+    let all_innovations: HashSet<usize> = innov1.keys().chain(innov2.keys()).copied().collect();
+    for innov in all_innovations {
+        match (innov1.get(&innov), innov2.get(&innov)) {
+            (Some(a), Some(b)) => {
+                matching += 1;
+                weight_diff += (a.weight - b.weight).abs();
+            }
+            (Some(_), None) => {
+                if innov > max_innov2 {
+                    excess += 1;
+                } else {
+                    disjoint += 1;
+                }
+            }
+            (None, Some(_)) => {
+                if innov > max_innov1 {
+                    excess += 1;
+                } else {
+                    disjoint += 1;
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    let n = g1.connections.len().max(g2.connections.len()).max(1) as f64;
+    let mean_weight_diff = if matching > 0 {
+        weight_diff / matching as f64
+    } else {
+        0.0
+    };
+
+    c1 * excess as f64 / n + c2 * disjoint as f64 / n + c3 * mean_weight_diff
+}
+
+pub struct SpeciesConfig {
+    pub c1: f64,
+    pub c2: f64,
+    pub c3: f64,
+    pub compatibility_threshold: f64,
+}
+
+impl Default for SpeciesConfig {
+    fn default() -> Self {
+        Self {
+            c1: 1.0,
+            c2: 1.0,
+            c3: 0.4,
+            compatibility_threshold: 3.0,
+        }
+    }
+}
+
+struct Species {
+    representative: Genome,
+    members: Vec<usize>,
+}
+
+pub struct Population {
+    genomes: Vec<Genome>,
+    innovation: InnovationTracker,
+    species: Vec<Species>,
+    config: SpeciesConfig,
+    n_inputs: usize,
+    n_outputs: usize,
+}
+
+impl Population {
+    pub fn new(n_inputs: usize, n_outputs: usize, size: usize) -> Self {
+        let mut innovation = InnovationTracker::new();
+        let genomes = (0..size)
+            .map(|_| Genome::new(n_inputs, n_outputs, &mut innovation))
+            .collect();
+
+        Self {
+            genomes,
+            innovation,
+            species: Vec::new(),
+            config: SpeciesConfig::default(),
+            n_inputs,
+            n_outputs,
+        }
+    }
+
+    pub fn genomes(&self) -> &[Genome] {
+        &self.genomes
+    }
+
+    fn speciate(&mut self) {
+        let mut species: Vec<Species> = Vec::new();
+
+        for (idx, genome) in self.genomes.iter().enumerate() {
+            let existing = species.iter_mut().find(|s| {
+                compatibility_distance(
+                    genome,
+                    &s.representative,
+                    self.config.c1,
+                    self.config.c2,
+                    self.config.c3,
+                ) < self.config.compatibility_threshold
+            });
+
+            match existing {
+                Some(s) => s.members.push(idx),
+                None => species.push(Species {
+                    representative: genome.clone(),
+                    members: vec![idx],
+                }),
+            }
+        }
+
+        self.species = species;
+    }
+
+    /// Evaluates `fitness_fn` over the population, applies fitness sharing
+    /// within species, then breeds the next generation via crossover and the
+    /// NEAT mutations, keeping population size fixed.
+    pub fn evolve_generation<F: Fn(&Genome) -> f64>(&mut self, fitness_fn: F) {
+        let fitnesses: Vec<f64> = self.genomes.iter().map(&fitness_fn).collect();
+        self.speciate();
+
+        let shared_fitness: Vec<f64> = (0..self.genomes.len())
+            .map(|idx| {
+                let species_size = self
+                    .species
+                    .iter()
+                    .find(|s| s.members.contains(&idx))
+                    .map_or(1, |s| s.members.len());
+                fitnesses[idx] / species_size as f64
+            })
+            .collect();
+
+        let pop_size = self.genomes.len();
+        let total_shared: f64 = shared_fitness.iter().sum();
+        let mut next_gen = Vec::with_capacity(pop_size);
+        let mut rng = rand::thread_rng();
+
+        for species in &self.species {
+            if species.members.is_empty() {
+                continue;
+            }
+
+            let species_shared: f64 = species.members.iter().map(|&i| shared_fitness[i]).sum();
+            let offspring_count = if total_shared > 0.0 {
+                ((species_shared / total_shared) * pop_size as f64)
+                    .round()
+                    .max(1.0) as usize
+            } else {
+                (pop_size / self.species.len().max(1)).max(1)
+            };
+
+            let mut ranked = species.members.clone();
+            ranked.sort_by(|&a, &b| shared_fitness[b].partial_cmp(&shared_fitness[a]).unwrap());
+
+            for _ in 0..offspring_count {
+                if next_gen.len() >= pop_size {
+                    break;
+                }
+
+                let parent_a = ranked[rng.gen_range(0..ranked.len())];
+                let parent_b = ranked[rng.gen_range(0..ranked.len())];
+
+                let mut child = if fitnesses[parent_a] >= fitnesses[parent_b] {
+                    crossover(&self.genomes[parent_a], &self.genomes[parent_b])
+                } else {
+                    crossover(&self.genomes[parent_b], &self.genomes[parent_a])
+                };
+
+                child.mutate_weights();
+                if rng.gen_bool(0.05) {
+                    child.mutate_add_connection(&mut self.innovation);
+                }
+                if rng.gen_bool(0.03) {
+                    child.mutate_add_node(&mut self.innovation);
+                }
+
+                next_gen.push(child);
+            }
+        }
+
+        while next_gen.len() < pop_size {
+            next_gen.push(Genome::new(self.n_inputs, self.n_outputs, &mut self.innovation));
+        }
+        next_gen.truncate(pop_size);
+
+        self.genomes = next_gen;
+    }
+}
@@ -0,0 +1,5 @@
+pub mod engine;
+pub mod evolve;
+pub mod nn;
+pub mod optim;
+pub mod tensor;
@@ -1,7 +1,7 @@
-use std::iter::zip;
-
 use micrograd_rs::engine::{Op, Value};
 use micrograd_rs::nn::{Layer, Neuron, ZeroGrad, MLP};
+use micrograd_rs::optim::{Loss, Optimizer, MSE, SGD};
+use micrograd_rs::tensor::Tensor;
 
 fn main() {
     let x = Value::new(1.0, vec![], None);
@@ -23,41 +23,27 @@ fn main() {
     // Desired targets.
     let ys = [1.0, -1.0, -1.0, 1.0];
 
-    // Convert inputs of f64 to Value.
-    let inputs: Vec<Vec<Value>> = xs
-        .iter()
-        .map(|xrow| {
-            vec![
-                Value::new(xrow[0], vec![], None),
-                Value::new(xrow[1], vec![], None),
-                Value::new(xrow[2], vec![], None),
-            ]
-        })
-        .collect();
+    // Whole batch of inputs as a single `4 x 3` tensor.
+    let input = Tensor::from_f64(vec![4, 3], &xs.iter().flatten().copied().collect::<Vec<f64>>());
 
     // MLP with three inputs, two 4-size layers, and single output.
     let mlp = MLP::new(3, &[4, 4, 1]);
+    let loss_fn = MSE;
+    let opt = SGD::new(0.01, 0.0);
 
     let mut ypred: Vec<Value> = Vec::new();
     for _ in 0..100 {
-        // Forward pass.
-        ypred = Vec::new();
-        for x in inputs.clone() {
-            ypred.push(mlp.forward(x)[0].clone());
-        }
+        // Forward pass over the whole batch at once.
+        ypred = mlp.forward_tensor(&input).data().to_vec();
         println!("{:#?}", ypred);
-        let loss = zip(ys, ypred.iter())
-            .map(|(ygt, yout)| (yout - ygt).pow(2.0))
-            .fold(Value::new(0.0, vec![], None), |a, b| a + b);
+        let loss = loss_fn.loss(&ypred, &ys);
 
         // Backward pass. Don't forget to reset grads.
         mlp.zero_grad();
         loss.backward();
 
         // Update.
-        for p in mlp.parameters() {
-            p.set_data(p.data() + (-0.01 * p.grad()));
-        }
+        opt.step(&mlp.parameters());
     }
 
     // Values data should be close to [1.0, -1.0, -1.0, 1.0].
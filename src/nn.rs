@@ -1,8 +1,18 @@
 use std::iter::zip;
+use std::io;
+use std::path::Path;
 
 use rand::{distributions::Uniform, prelude::Distribution};
+use serde::{Deserialize, Serialize};
 
 use crate::engine::Value;
+use crate::tensor::Tensor;
+
+/// Resets every parameter's accumulated gradient to zero before a backward
+/// pass, so gradients from the previous step don't keep piling up.
+pub trait ZeroGrad {
+    fn zero_grad(&self);
+}
 
 pub struct Neuron {
     weights: Vec<Value>,
@@ -36,6 +46,15 @@ impl Neuron {
     }
 }
 
+impl ZeroGrad for Neuron {
+    fn zero_grad(&self) {
+        for p in self.parameters() {
+            p.set_grad(0.0);
+        }
+        self.bias.set_grad(0.0);
+    }
+}
+
 pub struct Layer {
     neurons: Vec<Neuron>,
 }
@@ -51,6 +70,35 @@ impl Layer {
         self.neurons.iter().map(|n| n.forward(x.clone())).collect()
     }
 
+    /// Weight matrix, `nin x nout`, column `j` holding neuron `j`'s weights.
+    fn weight_tensor(&self) -> Tensor {
+        let nin = self.neurons[0].weights.len();
+        let nout = self.neurons.len();
+
+        let mut data = Vec::with_capacity(nin * nout);
+        for i in 0..nin {
+            for neuron in &self.neurons {
+                data.push(neuron.weights[i].clone());
+            }
+        }
+
+        Tensor::new(vec![nin, nout], data)
+    }
+
+    fn bias_tensor(&self) -> Tensor {
+        Tensor::new(
+            vec![1, self.neurons.len()],
+            self.neurons.iter().map(|n| n.bias.clone()).collect(),
+        )
+    }
+
+    /// Batched forward pass: `x` is `batch x nin`, result is `batch x nout`.
+    pub fn forward_tensor(&self, x: &Tensor) -> Tensor {
+        x.matmul(&self.weight_tensor())
+            .broadcast_add(&self.bias_tensor())
+            .tanh()
+    }
+
     pub fn parameters(&self) -> Vec<Value> {
         self.neurons
             .iter()
@@ -60,6 +108,14 @@ impl Layer {
     }
 }
 
+impl ZeroGrad for Layer {
+    fn zero_grad(&self) {
+        for neuron in &self.neurons {
+            neuron.zero_grad();
+        }
+    }
+}
+
 pub struct MLP {
     layers: Vec<Layer>,
 }
@@ -86,6 +142,17 @@ impl MLP {
         v
     }
 
+    /// Batched forward pass: `x` is `batch x nin`, result is `batch x nout`.
+    pub fn forward_tensor(&self, x: &Tensor) -> Tensor {
+        let mut v = x.clone();
+
+        for layer in &self.layers {
+            v = layer.forward_tensor(&v);
+        }
+
+        v
+    }
+
     pub fn parameters(&self) -> Vec<Value> {
         self.layers
             .iter()
@@ -94,3 +161,252 @@ impl MLP {
             .collect()
     }
 }
+
+impl ZeroGrad for MLP {
+    fn zero_grad(&self) {
+        for layer in &self.layers {
+            layer.zero_grad();
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct NeuronParams {
+    weights: Vec<f64>,
+    bias: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LayerParams {
+    neurons: Vec<NeuronParams>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MlpParams {
+    layers: Vec<LayerParams>,
+}
+
+impl Neuron {
+    fn params(&self) -> NeuronParams {
+        NeuronParams {
+            weights: self.weights.iter().map(|w| w.data()).collect(),
+            bias: self.bias.data(),
+        }
+    }
+
+    fn load_params(&self, params: &NeuronParams) {
+        for (w, data) in zip(self.weights.iter(), params.weights.iter()) {
+            w.set_data(*data);
+        }
+        self.bias.set_data(params.bias);
+    }
+}
+
+impl Layer {
+    fn params(&self) -> LayerParams {
+        LayerParams {
+            neurons: self.neurons.iter().map(|n| n.params()).collect(),
+        }
+    }
+
+    fn load_params(&self, params: &LayerParams) {
+        for (neuron, p) in zip(self.neurons.iter(), params.neurons.iter()) {
+            neuron.load_params(p);
+        }
+    }
+
+    fn matches_shape(&self, params: &LayerParams) -> bool {
+        self.neurons.len() == params.neurons.len()
+            && zip(&self.neurons, &params.neurons).all(|(n, np)| n.weights.len() == np.weights.len())
+    }
+}
+
+impl MLP {
+    /// Writes every weight/bias `data` value, in traversal order, to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let params = MlpParams {
+            layers: self.layers.iter().map(|l| l.params()).collect(),
+        };
+        let json = serde_json::to_string_pretty(&params)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        std::fs::write(path, json)
+    }
+
+    /// Rebuilds an `nin`/`nouts`-shaped network and restores the weights and
+    /// biases saved by `save`. Errors (rather than partially loading) if the
+    /// saved parameters don't match the requested shape.
+    pub fn load(path: impl AsRef<Path>, nin: usize, nouts: &[usize]) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        let params: MlpParams =
+            serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mlp = MLP::new(nin, nouts);
+        if !mlp.matches_shape(&params) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "saved parameters do not match the requested network shape",
+            ));
+        }
+
+        for (layer, p) in zip(mlp.layers.iter(), params.layers.iter()) {
+            layer.load_params(p);
+        }
+
+        Ok(mlp)
+    }
+
+    fn matches_shape(&self, params: &MlpParams) -> bool {
+        self.layers.len() == params.layers.len()
+            && zip(&self.layers, &params.layers).all(|(layer, lp)| layer.matches_shape(lp))
+    }
+}
+
+fn random_matrix(rows: usize, cols: usize) -> Vec<Vec<Value>> {
+    let mut rng = rand::thread_rng();
+    let between = Uniform::new_inclusive(-1.0, 1.0);
+    (0..rows)
+        .map(|_| {
+            between
+                .sample_iter(&mut rng)
+                .take(cols)
+                .map(|x| Value::new(x, vec![], None))
+                .collect()
+        })
+        .collect()
+}
+
+fn random_vector(n: usize) -> Vec<Value> {
+    let mut rng = rand::thread_rng();
+    let between = Uniform::new_inclusive(-1.0, 1.0);
+    between
+        .sample_iter(&mut rng)
+        .take(n)
+        .map(|x| Value::new(x, vec![], None))
+        .collect()
+}
+
+fn matvec(matrix: &[Vec<Value>], x: &[Value]) -> Vec<Value> {
+    matrix
+        .iter()
+        .map(|row| {
+            zip(row.iter(), x.iter())
+                .map(|(wi, xi)| wi * xi)
+                .fold(Value::new(0.0, vec![], None), |a, b| a + b)
+        })
+        .collect()
+}
+
+fn add_vec(a: &[Value], b: &[Value]) -> Vec<Value> {
+    zip(a, b).map(|(x, y)| x + y).collect()
+}
+
+fn hadamard(a: &[Value], b: &[Value]) -> Vec<Value> {
+    zip(a, b).map(|(x, y)| x * y).collect()
+}
+
+/// A single gated recurrent unit: given an input and the previous hidden
+/// state, produces the new hidden state via the standard GRU recurrence.
+pub struct GRUCell {
+    w_z: Vec<Vec<Value>>,
+    u_z: Vec<Vec<Value>>,
+    b_z: Vec<Value>,
+    w_r: Vec<Vec<Value>>,
+    u_r: Vec<Vec<Value>>,
+    b_r: Vec<Value>,
+    w_h: Vec<Vec<Value>>,
+    u_h: Vec<Vec<Value>>,
+    b_h: Vec<Value>,
+}
+
+impl GRUCell {
+    pub fn new(input_size: usize, hidden_size: usize) -> Self {
+        Self {
+            w_z: random_matrix(hidden_size, input_size),
+            u_z: random_matrix(hidden_size, hidden_size),
+            b_z: random_vector(hidden_size),
+            w_r: random_matrix(hidden_size, input_size),
+            u_r: random_matrix(hidden_size, hidden_size),
+            b_r: random_vector(hidden_size),
+            w_h: random_matrix(hidden_size, input_size),
+            u_h: random_matrix(hidden_size, hidden_size),
+            b_h: random_vector(hidden_size),
+        }
+    }
+
+    pub fn forward(&self, x: &[Value], h_prev: &[Value]) -> Vec<Value> {
+        let z: Vec<Value> = add_vec(
+            &add_vec(&matvec(&self.w_z, x), &matvec(&self.u_z, h_prev)),
+            &self.b_z,
+        )
+        .iter()
+        .map(|v| v.sigmoid())
+        .collect();
+
+        let r: Vec<Value> = add_vec(
+            &add_vec(&matvec(&self.w_r, x), &matvec(&self.u_r, h_prev)),
+            &self.b_r,
+        )
+        .iter()
+        .map(|v| v.sigmoid())
+        .collect();
+
+        let r_h = hadamard(&r, h_prev);
+        let h_tilde: Vec<Value> = add_vec(
+            &add_vec(&matvec(&self.w_h, x), &matvec(&self.u_h, &r_h)),
+            &self.b_h,
+        )
+        .iter()
+        .map(|v| v.tanh())
+        .collect();
+
+        let one_minus_z: Vec<Value> = z.iter().map(|zi| 1.0 - zi).collect();
+        add_vec(&hadamard(&one_minus_z, h_prev), &hadamard(&z, &h_tilde))
+    }
+
+    pub fn parameters(&self) -> Vec<Value> {
+        [&self.w_z, &self.u_z, &self.w_r, &self.u_r, &self.w_h, &self.u_h]
+            .iter()
+            .flat_map(|m| m.iter().flatten().cloned())
+            .chain(
+                [&self.b_z, &self.b_r, &self.b_h]
+                    .iter()
+                    .flat_map(|v| v.iter().cloned()),
+            )
+            .collect()
+    }
+}
+
+/// Unrolls a `GRUCell` over a sequence, starting from a zeroed hidden state,
+/// returning the hidden state produced at every timestep.
+pub struct GRU {
+    cell: GRUCell,
+    hidden_size: usize,
+}
+
+impl GRU {
+    pub fn new(input_size: usize, hidden_size: usize) -> Self {
+        Self {
+            cell: GRUCell::new(input_size, hidden_size),
+            hidden_size,
+        }
+    }
+
+    pub fn forward(&self, sequence: Vec<Vec<Value>>) -> Vec<Vec<Value>> {
+        let mut h: Vec<Value> = (0..self.hidden_size)
+            .map(|_| Value::new(0.0, vec![], None))
+            .collect();
+
+        let mut outputs = Vec::with_capacity(sequence.len());
+        for x in sequence {
+            h = self.cell.forward(&x, &h);
+            outputs.push(h.clone());
+        }
+
+        outputs
+    }
+
+    pub fn parameters(&self) -> Vec<Value> {
+        self.cell.parameters()
+    }
+}
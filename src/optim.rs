@@ -0,0 +1,150 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::iter::zip;
+
+use crate::engine::Value;
+
+pub trait Loss {
+    fn loss(&self, ypred: &[Value], ygt: &[f64]) -> Value;
+}
+
+pub struct MSE;
+
+impl Loss for MSE {
+    fn loss(&self, ypred: &[Value], ygt: &[f64]) -> Value {
+        let n = ypred.len() as f64;
+        let sum = zip(ygt, ypred)
+            .map(|(yg, yp)| (yp - *yg).pow(2.0))
+            .fold(Value::new(0.0, vec![], None), |a, b| a + b);
+
+        sum * (1.0 / n)
+    }
+}
+
+pub struct MAE;
+
+impl Loss for MAE {
+    fn loss(&self, ypred: &[Value], ygt: &[f64]) -> Value {
+        let n = ypred.len() as f64;
+        // |diff| via sqrt(diff^2 + eps): Op::ReLU's backward gates on the
+        // upstream gradient's sign rather than the forward value's, so
+        // relu(diff) + relu(-diff) cancels to a zero gradient everywhere,
+        // not just at diff == 0. The eps keeps pow(0.5)'s gradient finite
+        // at diff == 0 without relying on ReLU.
+        let eps = 1e-12;
+        let sum = zip(ygt, ypred)
+            .map(|(yg, yp)| ((yp - *yg).pow(2.0) + eps).pow(0.5))
+            .fold(Value::new(0.0, vec![], None), |a, b| a + b);
+
+        sum * (1.0 / n)
+    }
+}
+
+pub struct CrossEntropy;
+
+/// Clamps a probability away from the exact ends of `(0, 1)` so `ln` can
+/// never see `0.0` (`Value::ln`'s backward would also blow up there).
+fn clamp_prob(p: Value) -> Value {
+    let eps = 1e-12;
+    if p.data() < eps {
+        Value::new(eps, vec![], None)
+    } else if p.data() > 1.0 - eps {
+        Value::new(1.0 - eps, vec![], None)
+    } else {
+        p
+    }
+}
+
+impl Loss for CrossEntropy {
+    /// Treats `ypred` as logits rather than probabilities, squashing each
+    /// through a sigmoid first. `MLP`'s output layer is `tanh`-bounded to
+    /// `(-1, 1)`, not a probability, so reading it directly as `p` in
+    /// `y*ln(p) + (1-y)*ln(1-p)` produces negative "probabilities" and NaNs
+    /// for roughly half of all predictions; `sigmoid` keeps any finite input
+    /// (tanh-bounded or not) in the valid `(0, 1)` domain.
+    fn loss(&self, ypred: &[Value], ygt: &[f64]) -> Value {
+        let n = ypred.len() as f64;
+        let sum = zip(ygt, ypred)
+            .map(|(yg, logit)| {
+                let p = clamp_prob(logit.sigmoid());
+                -(*yg * p.ln() + (1.0 - *yg) * (1.0 - &p).ln())
+            })
+            .fold(Value::new(0.0, vec![], None), |a, b| a + b);
+
+        sum * (1.0 / n)
+    }
+}
+
+pub trait Optimizer {
+    fn step(&self, params: &[Value]);
+}
+
+pub struct SGD {
+    lr: f64,
+    momentum: f64,
+    velocity: RefCell<HashMap<Value, f64>>,
+}
+
+impl SGD {
+    pub fn new(lr: f64, momentum: f64) -> Self {
+        Self {
+            lr,
+            momentum,
+            velocity: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl Optimizer for SGD {
+    fn step(&self, params: &[Value]) {
+        let mut velocity = self.velocity.borrow_mut();
+        for p in params {
+            let v = velocity.entry(p.clone()).or_insert(0.0);
+            *v = self.momentum * *v - self.lr * p.grad();
+            p.set_data(p.data() + *v);
+        }
+    }
+}
+
+pub struct Adam {
+    lr: f64,
+    beta1: f64,
+    beta2: f64,
+    eps: f64,
+    t: RefCell<i32>,
+    moments: RefCell<HashMap<Value, (f64, f64)>>,
+}
+
+impl Adam {
+    pub fn new(lr: f64, beta1: f64, beta2: f64, eps: f64) -> Self {
+        Self {
+            lr,
+            beta1,
+            beta2,
+            eps,
+            t: RefCell::new(0),
+            moments: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl Optimizer for Adam {
+    fn step(&self, params: &[Value]) {
+        let mut t = self.t.borrow_mut();
+        *t += 1;
+
+        let mut moments = self.moments.borrow_mut();
+        for p in params {
+            let (m, v) = moments.entry(p.clone()).or_insert((0.0, 0.0));
+            let g = p.grad();
+
+            *m = self.beta1 * *m + (1.0 - self.beta1) * g;
+            *v = self.beta2 * *v + (1.0 - self.beta2) * g * g;
+
+            let m_hat = *m / (1.0 - self.beta1.powi(*t));
+            let v_hat = *v / (1.0 - self.beta2.powi(*t));
+
+            p.set_data(p.data() - self.lr * m_hat / (v_hat.sqrt() + self.eps));
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::engine::Value;
+
+/// A dense n-dimensional array of `Value`s. Matrix multiply and broadcasting
+/// are implemented for the 2D case, which is all `MLP::forward_tensor` needs
+/// to run a whole batch of rows through each layer at once. Every element is
+/// still an ordinary `Value`, so `backward()` works on the results unchanged.
+#[derive(Clone)]
+pub struct Tensor {
+    shape: Vec<usize>,
+    data: Vec<Value>,
+}
+
+impl Tensor {
+    pub fn new(shape: Vec<usize>, data: Vec<Value>) -> Self {
+        let expected: usize = shape.iter().product();
+        assert_eq!(expected, data.len(), "shape does not match data length");
+
+        Self { shape, data }
+    }
+
+    pub fn from_f64(shape: Vec<usize>, data: &[f64]) -> Self {
+        Self::new(
+            shape,
+            data.iter().map(|x| Value::new(*x, vec![], None)).collect(),
+        )
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    pub fn data(&self) -> &[Value] {
+        &self.data
+    }
+
+    fn rows(&self) -> usize {
+        self.shape[0]
+    }
+
+    fn cols(&self) -> usize {
+        self.shape[1]
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Value {
+        self.data[row * self.cols() + col].clone()
+    }
+
+    /// 2D matrix multiply: `self` is `m x k`, `other` is `k x n`, result is `m x n`.
+    pub fn matmul(&self, other: &Tensor) -> Tensor {
+        assert_eq!(self.shape.len(), 2, "matmul only supports 2D tensors");
+        assert_eq!(other.shape.len(), 2, "matmul only supports 2D tensors");
+        assert_eq!(self.cols(), other.rows(), "inner dimensions must match");
+
+        let m = self.rows();
+        let n = other.cols();
+        let k = self.cols();
+
+        let mut out = Vec::with_capacity(m * n);
+        for i in 0..m {
+            for j in 0..n {
+                let mut acc = Value::new(0.0, vec![], None);
+                for p in 0..k {
+                    acc = acc + &self.get(i, p) * &other.get(p, j);
+                }
+                out.push(acc);
+            }
+        }
+
+        Tensor::new(vec![m, n], out)
+    }
+
+    /// Adds a `1 x n` row vector to every row, or an `m x 1` column vector to
+    /// every column, of a `m x n` tensor.
+    pub fn broadcast_add(&self, bias: &Tensor) -> Tensor {
+        assert_eq!(self.shape.len(), 2, "broadcast_add only supports 2D tensors");
+
+        let m = self.rows();
+        let n = self.cols();
+
+        let data = if bias.shape == [1, n] {
+            (0..m)
+                .flat_map(|i| (0..n).map(move |j| (i, j)))
+                .map(|(i, j)| &self.get(i, j) + &bias.get(0, j))
+                .collect()
+        } else if bias.shape == [m, 1] {
+            (0..m)
+                .flat_map(|i| (0..n).map(move |j| (i, j)))
+                .map(|(i, j)| &self.get(i, j) + &bias.get(i, 0))
+                .collect()
+        } else {
+            panic!("bias shape {:?} does not broadcast against {:?}", bias.shape, self.shape);
+        };
+
+        Tensor::new(vec![m, n], data)
+    }
+
+    pub fn relu(&self) -> Tensor {
+        Tensor::new(self.shape.clone(), self.data.iter().map(|v| v.relu()).collect())
+    }
+
+    pub fn tanh(&self) -> Tensor {
+        Tensor::new(self.shape.clone(), self.data.iter().map(|v| v.tanh()).collect())
+    }
+
+    pub fn parameters(&self) -> Vec<Value> {
+        self.data.clone()
+    }
+}
+
+impl Add for &Tensor {
+    type Output = Tensor;
+    fn add(self, other: &Tensor) -> Tensor {
+        assert_eq!(self.shape, other.shape, "shape mismatch in element-wise add");
+        let data = self.data.iter().zip(&other.data).map(|(a, b)| a + b).collect();
+        Tensor::new(self.shape.clone(), data)
+    }
+}
+
+impl Sub for &Tensor {
+    type Output = Tensor;
+    fn sub(self, other: &Tensor) -> Tensor {
+        assert_eq!(self.shape, other.shape, "shape mismatch in element-wise sub");
+        let data = self.data.iter().zip(&other.data).map(|(a, b)| a - b).collect();
+        Tensor::new(self.shape.clone(), data)
+    }
+}
+
+impl Mul for &Tensor {
+    type Output = Tensor;
+    fn mul(self, other: &Tensor) -> Tensor {
+        assert_eq!(self.shape, other.shape, "shape mismatch in element-wise mul");
+        let data = self.data.iter().zip(&other.data).map(|(a, b)| a * b).collect();
+        Tensor::new(self.shape.clone(), data)
+    }
+}